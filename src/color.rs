@@ -0,0 +1,91 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serenity::utils::Colour;
+
+use crate::css_colors::named_color;
+
+static HEX_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^#([A-Fa-f0-9]{2})([A-Fa-f0-9]{2})([A-Fa-f0-9]{2})$").unwrap());
+
+static SHORT_HEX_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^#([A-Fa-f0-9])([A-Fa-f0-9])([A-Fa-f0-9])$").unwrap());
+
+static RGB_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^rgb\(\s*(\d{1,3})\s*,\s*(\d{1,3})\s*,\s*(\d{1,3})\s*\)$").unwrap());
+
+static HSL_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^hsl\(\s*(\d{1,3})\s*,\s*(\d{1,3})%\s*,\s*(\d{1,3})%\s*\)$").unwrap());
+
+fn parse_hex(input: &str) -> Option<Colour> {
+    let captures = HEX_REGEX.captures(input)?;
+    let r = u8::from_str_radix(captures.get(1)?.as_str(), 16).ok()?;
+    let g = u8::from_str_radix(captures.get(2)?.as_str(), 16).ok()?;
+    let b = u8::from_str_radix(captures.get(3)?.as_str(), 16).ok()?;
+    Some(Colour::from_rgb(r, g, b))
+}
+
+fn parse_short_hex(input: &str) -> Option<Colour> {
+    let captures = SHORT_HEX_REGEX.captures(input)?;
+    let double = |s: &str| u8::from_str_radix(&s.repeat(2), 16).ok();
+    let r = double(captures.get(1)?.as_str())?;
+    let g = double(captures.get(2)?.as_str())?;
+    let b = double(captures.get(3)?.as_str())?;
+    Some(Colour::from_rgb(r, g, b))
+}
+
+fn parse_rgb(input: &str) -> Option<Colour> {
+    let captures = RGB_REGEX.captures(input)?;
+    let component = |i: usize| captures.get(i)?.as_str().parse::<u16>().ok().filter(|v| *v <= 255);
+    let r = component(1)? as u8;
+    let g = component(2)? as u8;
+    let b = component(3)? as u8;
+    Some(Colour::from_rgb(r, g, b))
+}
+
+/// Convert HSL (H in degrees, S/L in percent) to an RGB [`Colour`].
+fn hsl_to_rgb(h: f32, s: f32, l: f32) -> Colour {
+    let h = h.rem_euclid(360.0);
+    let s = (s / 100.0).clamp(0.0, 1.0);
+    let l = (l / 100.0).clamp(0.0, 1.0);
+
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let x = c * (1.0 - ((h / 60.0).rem_euclid(2.0) - 1.0).abs());
+    let m = l - c / 2.0;
+
+    let (r, g, b) = match h as u32 {
+        0..=59 => (c, x, 0.0),
+        60..=119 => (x, c, 0.0),
+        120..=179 => (0.0, c, x),
+        180..=239 => (0.0, x, c),
+        240..=299 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+
+    Colour::from_rgb(
+        ((r + m) * 255.0).round() as u8,
+        ((g + m) * 255.0).round() as u8,
+        ((b + m) * 255.0).round() as u8,
+    )
+}
+
+fn parse_hsl(input: &str) -> Option<Colour> {
+    let captures = HSL_REGEX.captures(input)?;
+    let h = captures.get(1)?.as_str().parse::<f32>().ok()?;
+    let s = captures.get(2)?.as_str().parse::<f32>().ok()?;
+    let l = captures.get(3)?.as_str().parse::<f32>().ok()?;
+    Some(hsl_to_rgb(h, s, l))
+}
+
+/// Parse a user-supplied color string into a [`Colour`].
+///
+/// Accepts `#RRGGBB` and `#RGB` hex notation, `rgb(r, g, b)` and
+/// `hsl(h, s%, l%)` functional notation, and CSS named colors
+/// (e.g. `rebeccapurple`).
+pub fn parse_color(input: &str) -> Option<Colour> {
+    let input = input.trim();
+    parse_hex(input)
+        .or_else(|| parse_short_hex(input))
+        .or_else(|| parse_rgb(input))
+        .or_else(|| parse_hsl(input))
+        .or_else(|| named_color(input).map(Colour::from))
+}
@@ -0,0 +1,269 @@
+use serenity::framework::standard::macros::{command, group};
+use serenity::framework::standard::{Args, CommandResult};
+use serenity::model::channel::Message;
+use serenity::model::mention::Mentionable;
+use serenity::prelude::*;
+
+use crate::color::parse_color;
+use crate::config::{guild_config, Configs};
+use crate::roles::{audit_roles, cleanup_roles};
+
+#[group]
+#[prefix("config")]
+#[commands(show, contrast, role, icons, background)]
+#[required_permissions(ADMINISTRATOR)]
+#[only_in(guilds)]
+pub struct ConfigCommands;
+
+#[group]
+#[prefix("colors")]
+#[commands(list, prune)]
+#[required_permissions(ADMINISTRATOR)]
+#[only_in(guilds)]
+pub struct ColorAdminCommands;
+
+/// Show the color settings for this server.
+#[command]
+async fn show(ctx: &Context, msg: &Message) -> CommandResult {
+    let guild_id = msg.guild_id.expect("config commands are guild-only");
+    let config = guild_config(ctx, guild_id).await;
+
+    let backgrounds = config
+        .backgrounds
+        .iter()
+        .map(|bg| format!("#{:06X}", bg))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    msg.reply(
+        ctx,
+        format!(
+            "min_contrast: {}\nbackgrounds: {}\nbase_role: \"{}\"\nrole_icons: {}",
+            config.min_contrast, backgrounds, config.base_role, config.role_icons
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Set the minimum contrast ratio required for colors in this server.
+#[command]
+#[min_args(1)]
+async fn contrast(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.expect("config commands are guild-only");
+    let min_contrast: f32 = match args.single() {
+        Ok(value) => value,
+        Err(_) => {
+            msg.reply(ctx, "Usage: `/config contrast <value>`").await?;
+            return Ok(());
+        }
+    };
+    if !min_contrast.is_finite() || min_contrast <= 0.0 {
+        msg.reply(ctx, "min_contrast must be a finite number greater than 0")
+            .await?;
+        return Ok(());
+    }
+
+    let data = ctx.data.read().await;
+    let configs = data.get::<Configs>().expect("Configs not initialized").clone();
+    let mut configs = configs.write().await;
+    let (config, saved) = configs.update(guild_id, |c| c.min_contrast = min_contrast).await;
+
+    match saved {
+        Ok(()) => {
+            msg.reply(ctx, format!("min_contrast set to {}", config.min_contrast))
+                .await?;
+        }
+        Err(err) => {
+            msg.reply(ctx, format!("min_contrast set to {} but failed to save: {}", config.min_contrast, err))
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Set the base role new color roles are inserted above, e.g. `/config role colors`.
+#[command]
+#[min_args(1)]
+async fn role(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.expect("config commands are guild-only");
+    let name = args.rest().trim().to_string();
+
+    let data = ctx.data.read().await;
+    let configs = data.get::<Configs>().expect("Configs not initialized").clone();
+    let mut configs = configs.write().await;
+    let (config, saved) = configs.update(guild_id, |c| c.base_role = name.clone()).await;
+
+    match saved {
+        Ok(()) => {
+            msg.reply(ctx, format!("base_role set to \"{}\"", config.base_role))
+                .await?;
+        }
+        Err(err) => {
+            msg.reply(
+                ctx,
+                format!("base_role set to \"{}\" but failed to save: {}", config.base_role, err),
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Toggle generating a solid-color icon for new color roles, e.g.
+/// `/config icons on`. Only takes effect on guilds with the `ROLE_ICONS`
+/// boost feature.
+#[command]
+#[min_args(1)]
+async fn icons(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.expect("config commands are guild-only");
+    let enabled = match args.single::<String>()?.to_ascii_lowercase().as_str() {
+        "on" | "true" | "enable" => true,
+        "off" | "false" | "disable" => false,
+        _ => {
+            msg.reply(ctx, "Usage: `/config icons <on|off>`").await?;
+            return Ok(());
+        }
+    };
+
+    let data = ctx.data.read().await;
+    let configs = data.get::<Configs>().expect("Configs not initialized").clone();
+    let mut configs = configs.write().await;
+    let (config, saved) = configs.update(guild_id, |c| c.role_icons = enabled).await;
+
+    match saved {
+        Ok(()) => {
+            msg.reply(ctx, format!("role_icons set to {}", config.role_icons))
+                .await?;
+        }
+        Err(err) => {
+            msg.reply(
+                ctx,
+                format!("role_icons set to {} but failed to save: {}", config.role_icons, err),
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Set the backgrounds colors are checked against for contrast, e.g.
+/// `/config background #313338 #FFFFFF`.
+#[command]
+#[min_args(1)]
+async fn background(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.expect("config commands are guild-only");
+
+    let mut backgrounds = Vec::new();
+    for arg in args.iter::<String>() {
+        let arg: String = arg?;
+        match parse_color(&arg) {
+            Some(color) => backgrounds.push(color.0),
+            None => {
+                msg.reply(ctx, format!("I don't understand the color `{}`", arg))
+                    .await?;
+                return Ok(());
+            }
+        }
+    }
+
+    let data = ctx.data.read().await;
+    let configs = data.get::<Configs>().expect("Configs not initialized").clone();
+    let mut configs = configs.write().await;
+    let (config, saved) = configs.update(guild_id, |c| c.backgrounds = backgrounds.clone()).await;
+
+    let rendered = config
+        .backgrounds
+        .iter()
+        .map(|bg| format!("#{:06X}", bg))
+        .collect::<Vec<_>>()
+        .join(", ");
+    match saved {
+        Ok(()) => {
+            msg.reply(ctx, format!("backgrounds set to {}", rendered)).await?;
+        }
+        Err(err) => {
+            msg.reply(
+                ctx,
+                format!("backgrounds set to {} but failed to save: {}", rendered, err),
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// List every color role and its holders, e.g. `/colors list` or
+/// `/colors list failing` to only show roles at or below the current
+/// min_contrast, i.e. colors `/color` would now reject.
+#[command]
+async fn list(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let guild = match msg.guild(&ctx.cache) {
+        Some(guild) => guild,
+        None => {
+            msg.reply(ctx, "This command can only be used in a server")
+                .await?;
+            return Ok(());
+        }
+    };
+    let config = guild_config(ctx, guild.id).await;
+    let only_failing = args.rest().trim().eq_ignore_ascii_case("failing");
+
+    let mut audits = audit_roles(&guild, &config);
+    if only_failing {
+        audits.retain(|audit| audit.contrast <= config.min_contrast);
+    }
+    audits.sort_by(|a, b| a.role.name.cmp(&b.role.name));
+
+    if audits.is_empty() {
+        msg.reply(ctx, "No matching color roles").await?;
+        return Ok(());
+    }
+
+    let body = audits
+        .iter()
+        .map(|audit| {
+            let holders = if audit.holders.is_empty() {
+                "nobody".to_string()
+            } else {
+                audit
+                    .holders
+                    .iter()
+                    .map(|holder| holder.mention().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            format!(
+                "{} - contrast {:.2} - {}",
+                audit.role.name, audit.contrast, holders
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    // The holders are rendered as mentions so they're clickable, but this is
+    // an audit listing, not a summons - don't actually ping anyone.
+    msg.channel_id
+        .send_message(ctx, |m| m.content(body).allowed_mentions(|am| am.empty_users()))
+        .await?;
+    Ok(())
+}
+
+/// Force-run the empty color role cleanup now, instead of waiting for it to
+/// fire as a side effect of the next assignment.
+#[command]
+async fn prune(ctx: &Context, msg: &Message) -> CommandResult {
+    let guild = match msg.guild(&ctx.cache) {
+        Some(guild) => guild,
+        None => {
+            msg.reply(ctx, "This command can only be used in a server")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let pruned = cleanup_roles(ctx, &guild, None).await?;
+    msg.reply(ctx, format!("Pruned {} empty color role(s)", pruned.len()))
+        .await?;
+    Ok(())
+}
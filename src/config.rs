@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Arc;
+
+use err_derive::Error;
+use serde::{Deserialize, Serialize};
+use serenity::model::id::GuildId;
+use serenity::prelude::*;
+
+const CONFIG_PATH: &str = "guild_data.toml";
+
+#[derive(Debug, Error)]
+pub enum SaveError {
+    #[error(display = "failed to serialize config: {}", _0)]
+    Serialize(#[error(cause)] toml::ser::Error),
+    #[error(display = "failed to write config: {}", _0)]
+    Io(#[error(cause)] std::io::Error),
+}
+
+fn default_min_contrast() -> f32 {
+    2.0
+}
+
+/// Discord's dark and light theme chat backgrounds; both are checked so a
+/// color is only accepted if it's legible under either theme.
+fn default_backgrounds() -> Vec<u32> {
+    vec![0x313338, 0xFFFFFF]
+}
+
+fn default_base_role() -> String {
+    "colors".to_string()
+}
+
+/// Per-guild settings for the color role assignment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuildConfig {
+    #[serde(default = "default_min_contrast")]
+    pub min_contrast: f32,
+    #[serde(default = "default_backgrounds")]
+    pub backgrounds: Vec<u32>,
+    #[serde(default = "default_base_role")]
+    pub base_role: String,
+    /// Whether to generate and upload a solid-color icon for new color
+    /// roles. Only takes effect on guilds with the `ROLE_ICONS` feature.
+    #[serde(default)]
+    pub role_icons: bool,
+}
+
+impl Default for GuildConfig {
+    fn default() -> Self {
+        GuildConfig {
+            min_contrast: default_min_contrast(),
+            backgrounds: default_backgrounds(),
+            base_role: default_base_role(),
+            role_icons: false,
+        }
+    }
+}
+
+/// Loads and persists [`GuildConfig`]s, keyed by guild id, to `guild_data.toml`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ConfigStore {
+    guilds: HashMap<String, GuildConfig>,
+}
+
+impl ConfigStore {
+    pub fn load() -> Self {
+        fs::read_to_string(CONFIG_PATH)
+            .ok()
+            .and_then(|data| toml::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    async fn save(&self) -> Result<(), SaveError> {
+        let data = toml::to_string_pretty(self)?;
+        tokio::fs::write(CONFIG_PATH, data).await?;
+        Ok(())
+    }
+
+    pub fn get(&self, guild: GuildId) -> GuildConfig {
+        self.guilds
+            .get(&guild.0.to_string())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Apply `f` to `guild`'s config and persist the result. Returns the
+    /// updated config together with the outcome of the save so callers can
+    /// tell the user if their change didn't actually get written to disk.
+    pub async fn update(
+        &mut self,
+        guild: GuildId,
+        f: impl FnOnce(&mut GuildConfig),
+    ) -> (GuildConfig, Result<(), SaveError>) {
+        let mut config = self.get(guild);
+        f(&mut config);
+        self.guilds.insert(guild.0.to_string(), config.clone());
+        let saved = self.save().await;
+        (config, saved)
+    }
+}
+
+pub struct Configs;
+
+impl TypeMapKey for Configs {
+    type Value = Arc<RwLock<ConfigStore>>;
+}
+
+/// Look up the settings for `guild`, falling back to defaults if it has
+/// never been configured.
+pub async fn guild_config(ctx: &Context, guild: GuildId) -> GuildConfig {
+    let data = ctx.data.read().await;
+    let configs = data.get::<Configs>().expect("Configs not initialized").clone();
+    let configs = configs.read().await;
+    configs.get(guild)
+}
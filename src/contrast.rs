@@ -0,0 +1,37 @@
+use serenity::utils::Colour;
+
+/// Linearize a single sRGB channel value in `[0, 1]`, per the WCAG 2.x
+/// relative luminance formula.
+fn linearize(c: f32) -> f32 {
+    if c <= 0.03928 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// The WCAG 2.x relative luminance of a color.
+fn relative_luminance(color: Colour) -> f32 {
+    let (r, g, b) = color.tuple();
+    let r = linearize(r as f32 / 255.0);
+    let g = linearize(g as f32 / 255.0);
+    let b = linearize(b as f32 / 255.0);
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// The WCAG 2.x contrast ratio between two colors.
+fn contrast_ratio(a: Colour, b: Colour) -> f32 {
+    let (l1, l2) = (relative_luminance(a), relative_luminance(b));
+    let (lighter, darker) = if l1 > l2 { (l1, l2) } else { (l2, l1) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// Measure the contrast of `color` against each of `backgrounds`, returning
+/// the worst (lowest) WCAG contrast ratio found. Passing both Discord's dark
+/// and light theme backgrounds guarantees the color is legible in either.
+pub fn background_contrast(color: Colour, backgrounds: &[u32]) -> f32 {
+    backgrounds
+        .iter()
+        .map(|&bg| contrast_ratio(color, Colour::from(bg)))
+        .fold(f32::INFINITY, f32::min)
+}
@@ -0,0 +1,19 @@
+use image::{Rgb, RgbImage};
+use serenity::utils::Colour;
+
+/// Discord role icons are rendered small; a 128x128 square is plenty.
+const ICON_SIZE: u32 = 128;
+
+/// Render a solid-color square, encoded as PNG bytes suitable for
+/// [`serenity::builder::EditRole::icon`], which does its own base64
+/// encoding.
+pub fn solid_color_icon(color: Colour) -> Vec<u8> {
+    let (r, g, b) = color.tuple();
+    let image = RgbImage::from_pixel(ICON_SIZE, ICON_SIZE, Rgb([r, g, b]));
+
+    let mut bytes = Vec::new();
+    image
+        .write_to(&mut std::io::Cursor::new(&mut bytes), image::ImageOutputFormat::Png)
+        .expect("encoding a solid color PNG never fails");
+    bytes
+}
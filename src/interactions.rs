@@ -0,0 +1,147 @@
+use serenity::builder::CreateEmbed;
+use serenity::model::application::command::{Command, CommandOptionType};
+use serenity::model::application::interaction::application_command::ApplicationCommandInteraction;
+use serenity::model::application::interaction::InteractionResponseType;
+use serenity::model::id::RoleId;
+use serenity::prelude::*;
+
+use crate::color::parse_color;
+use crate::config::guild_config;
+use crate::contrast::background_contrast;
+use crate::roles::{assign_color, cleanup_roles, color_regex};
+
+/// Register the `/color` application command globally.
+pub async fn register_commands(ctx: &Context) -> serenity::Result<()> {
+    Command::create_global_application_command(&ctx.http, |cmd| {
+        cmd.name("color")
+            .description("Assign yourself a color role, or \"clear\" to remove it")
+            .create_option(|opt| {
+                opt.name("value")
+                    .description("A color (#RRGGBB, a CSS name, rgb()/hsl(), or \"clear\")")
+                    .kind(CommandOptionType::String)
+                    .required(true)
+            })
+    })
+    .await?;
+    Ok(())
+}
+
+enum ColorReply {
+    Assigned(CreateEmbed),
+    Message(String),
+}
+
+async fn set_color(ctx: &Context, command: &ApplicationCommandInteraction, input: &str) -> ColorReply {
+    let color = match parse_color(input) {
+        Some(color) => color,
+        None => return ColorReply::Message(format!("I don't understand the color `{}`", input)),
+    };
+
+    let guild_id = match command.guild_id {
+        Some(id) => id,
+        None => return ColorReply::Message("This command can only be used in a server".to_string()),
+    };
+    let guild = match ctx.cache.guild(guild_id) {
+        Some(guild) => guild,
+        None => return ColorReply::Message("This command can only be used in a server".to_string()),
+    };
+
+    let config = guild_config(ctx, guild_id).await;
+    let contrast = background_contrast(color, &config.backgrounds);
+    if contrast <= config.min_contrast {
+        return ColorReply::Message(format!(
+            "That color doesn't have enough contrast against the background ({:.1} < {:.1})",
+            contrast, config.min_contrast
+        ));
+    }
+
+    match assign_color(ctx, &command.user, guild, color, &config).await {
+        Ok(role_name) => {
+            let mut embed = CreateEmbed::default();
+            embed
+                .title("Color assigned")
+                .description(format!("Assigned {} to {}", role_name, command.user.name))
+                .colour(color)
+                .field("Hex", format!("#{}", color.hex()), true)
+                .field("Contrast", format!("{:.2}", contrast), true);
+            ColorReply::Assigned(embed)
+        }
+        Err(err) => ColorReply::Message(format!("Failed to assign color: {}", err)),
+    }
+}
+
+async fn clear_color(ctx: &Context, command: &ApplicationCommandInteraction) -> ColorReply {
+    let guild_id = match command.guild_id {
+        Some(id) => id,
+        None => return ColorReply::Message("This command can only be used in a server".to_string()),
+    };
+    let guild = match ctx.cache.guild(guild_id) {
+        Some(guild) => guild,
+        None => return ColorReply::Message("This command can only be used in a server".to_string()),
+    };
+
+    let mut member = match guild.member(ctx, command.user.id).await {
+        Ok(member) => member,
+        Err(err) => return ColorReply::Message(format!("Failed to clear color: {}", err)),
+    };
+
+    let old_colors: Vec<RoleId> = member
+        .roles(&ctx.cache)
+        .unwrap_or_default()
+        .iter()
+        .filter(|r| color_regex().is_match(&r.name))
+        .map(|r| r.id)
+        .collect();
+
+    if let Err(err) = member.remove_roles(&ctx.http, &old_colors).await {
+        return ColorReply::Message(format!("Failed to clear color: {}", err));
+    }
+    if let Err(err) = cleanup_roles(ctx, &guild, None).await {
+        return ColorReply::Message(format!("Failed to clear color: {}", err));
+    }
+
+    ColorReply::Message("Cleared your color".to_string())
+}
+
+/// Handle a `/color` application command, replying with an ephemeral embed
+/// when a color is assigned (or a plain ephemeral message after a clear or on
+/// failure) so the channel stays clean.
+pub async fn handle_color_command(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+) -> serenity::Result<()> {
+    let input = command
+        .data
+        .options
+        .first()
+        .and_then(|opt| opt.value.as_ref())
+        .and_then(|value| value.as_str())
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+
+    let reply = if input.eq_ignore_ascii_case("clear") {
+        clear_color(ctx, command).await
+    } else {
+        set_color(ctx, command, &input).await
+    };
+
+    command
+        .create_interaction_response(&ctx.http, |response| {
+            response
+                .kind(InteractionResponseType::ChannelMessageWithSource)
+                .interaction_response_data(|data| {
+                    data.ephemeral(true);
+                    match reply {
+                        ColorReply::Assigned(embed) => {
+                            data.set_embed(embed);
+                        }
+                        ColorReply::Message(message) => {
+                            data.content(message);
+                        }
+                    }
+                    data
+                })
+        })
+        .await
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+use err_derive::Error;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serenity::builder::EditRole;
+use serenity::json::hashmap_to_json_map;
+use serenity::model::channel::AttachmentType;
+use serenity::model::guild::{Guild, Role};
+use serenity::model::id::{RoleId, UserId};
+use serenity::model::user::User;
+use serenity::prelude::*;
+use serenity::utils::Colour;
+use serenity::Error as DiscordError;
+
+use crate::config::GuildConfig;
+use crate::contrast::background_contrast;
+use crate::icon::solid_color_icon;
+
+#[derive(Debug, Error)]
+pub enum BotError {
+    #[error(display = "missing \"{}\" base role", _0)]
+    NoColorRole(String),
+    #[error(display = "discord error: {}", _0)]
+    DiscordError(#[error(cause)] Box<DiscordError>),
+}
+
+pub type Result<T> = std::result::Result<T, BotError>;
+
+static COLOR_ROLE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^#([A-Fa-f0-9]{2})([A-Fa-f0-9]{2})([A-Fa-f0-9]{2})$").unwrap());
+
+pub fn color_regex() -> &'static Regex {
+    &COLOR_ROLE_REGEX
+}
+
+fn get_color_role_position(guild: &Guild, config: &GuildConfig) -> Result<u8> {
+    guild
+        .role_by_name(&config.base_role)
+        .map(|r| r.position as u8)
+        .ok_or_else(|| BotError::NoColorRole(config.base_role.clone()))
+}
+
+pub async fn get_or_create_role(
+    ctx: &Context,
+    color: Colour,
+    guild: &Guild,
+    config: &GuildConfig,
+) -> Result<Role> {
+    let name = format!("#{}", color.hex());
+    if let Some(role) = guild.role_by_name(&name) {
+        return Ok(role.clone());
+    }
+
+    let color_position = get_color_role_position(guild, config)?;
+
+    // `EditRole::icon` is async (it has to fetch and encode the image data),
+    // so it can't be set through the sync closure `Guild::create_role` takes.
+    // Build the role manually instead and hit the HTTP endpoint directly.
+    let mut builder = EditRole::default();
+    builder.name(&name).colour(color.0 as u64).position(color_position);
+
+    if config.role_icons && guild.features.iter().any(|f| f == "ROLE_ICONS") {
+        let icon = AttachmentType::Bytes {
+            data: solid_color_icon(color).into(),
+            filename: "icon.png".to_string(),
+        };
+        builder.icon(&ctx.http, icon).await?;
+    }
+
+    let map = hashmap_to_json_map(builder.0);
+    let role = ctx.http.create_role(guild.id.0, &map, None).await?;
+
+    Ok(role)
+}
+
+pub async fn assign_color(
+    ctx: &Context,
+    user: &User,
+    guild: Guild,
+    color: Colour,
+    config: &GuildConfig,
+) -> Result<String> {
+    let role = get_or_create_role(ctx, color, &guild, config).await?;
+    let mut member = guild.member(ctx, user.id).await?;
+
+    let old_colors: Vec<RoleId> = member
+        .roles(&ctx.cache)
+        .unwrap_or_default()
+        .iter()
+        .filter(|r| color_regex().is_match(&r.name))
+        .map(|r| r.id)
+        .collect();
+    member.remove_roles(&ctx.http, &old_colors).await?;
+    member.add_role(&ctx.http, role.id).await?;
+    cleanup_roles(ctx, &guild, Some(role.id)).await?;
+    Ok(role.name)
+}
+
+/// Delete color roles with nobody wearing them, keeping `keep` (the role
+/// that was just assigned, if any) around even if the cache hasn't caught up.
+pub async fn cleanup_roles(ctx: &Context, guild: &Guild, keep: Option<RoleId>) -> Result<Vec<RoleId>> {
+    let used_roles: Vec<RoleId> = guild
+        .members
+        .values()
+        .flat_map(|member| member.roles.iter())
+        .cloned()
+        .collect();
+
+    let empty_roles: Vec<RoleId> = guild
+        .roles
+        .values()
+        .filter(|role| color_regex().is_match(&role.name))
+        .filter(|role| !used_roles.contains(&role.id))
+        .filter(|role| Some(role.id) != keep)
+        .map(|role| role.id)
+        .collect();
+
+    for &empty_role in &empty_roles {
+        guild.delete_role(ctx, empty_role).await?;
+    }
+    Ok(empty_roles)
+}
+
+/// A color role together with its measured contrast and current holders.
+pub struct RoleAudit {
+    pub role: Role,
+    pub contrast: f32,
+    pub holders: Vec<UserId>,
+}
+
+/// List every color role in `guild`, their measured contrast against
+/// `config`'s backgrounds, and the members currently wearing them.
+pub fn audit_roles(guild: &Guild, config: &GuildConfig) -> Vec<RoleAudit> {
+    let mut holders_by_role: HashMap<RoleId, Vec<UserId>> = HashMap::new();
+    for (&user_id, member) in &guild.members {
+        for &role_id in &member.roles {
+            holders_by_role.entry(role_id).or_default().push(user_id);
+        }
+    }
+
+    guild
+        .roles
+        .values()
+        .filter(|role| color_regex().is_match(&role.name))
+        .map(|role| RoleAudit {
+            role: role.clone(),
+            contrast: background_contrast(role.colour, &config.backgrounds),
+            holders: holders_by_role.get(&role.id).cloned().unwrap_or_default(),
+        })
+        .collect()
+}